@@ -0,0 +1,60 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, Result};
+use std::fmt;
+
+/// A struct that represents an account address.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct AccountAddress([u8; AccountAddress::LENGTH]);
+
+impl AccountAddress {
+    pub const LENGTH: usize = 16;
+
+    pub fn new(address: [u8; Self::LENGTH]) -> Self {
+        Self(address)
+    }
+
+    /// Parses a hex literal of the form `0x...`, zero-padding the digits on
+    /// the left so that shorthand addresses like `0x1` are accepted.
+    pub fn from_hex_literal(literal: &str) -> Result<Self> {
+        if !literal.starts_with("0x") && !literal.starts_with("0X") {
+            return Err(anyhow!("address literal must start with 0x: {}", literal));
+        }
+
+        let hex_digits = &literal[2..];
+        if hex_digits.is_empty() || hex_digits.len() > Self::LENGTH * 2 {
+            return Err(anyhow!("invalid address literal: {}", literal));
+        }
+
+        let padded = format!("{:0>width$}", hex_digits, width = Self::LENGTH * 2);
+        let bytes =
+            hex::decode(&padded).map_err(|_| anyhow!("invalid address literal: {}", literal))?;
+        let mut buf = [0u8; Self::LENGTH];
+        buf.copy_from_slice(&bytes);
+        Ok(Self(buf))
+    }
+
+    /// Renders the address as hex digits with leading zeros stripped and no
+    /// `0x` prefix, e.g. `1` rather than `00000000000000000000000000000001`.
+    pub fn short_str_lossless(&self) -> String {
+        let hex_str = hex::encode(self.0).trim_start_matches('0').to_string();
+        if hex_str.is_empty() {
+            "0".to_string()
+        } else {
+            hex_str
+        }
+    }
+}
+
+impl fmt::Display for AccountAddress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.short_str_lossless())
+    }
+}
+
+impl fmt::Debug for AccountAddress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "0x{}", hex::encode(self.0))
+    }
+}
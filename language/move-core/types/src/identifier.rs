@@ -0,0 +1,49 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, Result};
+use std::fmt;
+
+/// An owned, validated Move identifier (module name, struct name, field
+/// name, ...).
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct Identifier(Box<str>);
+
+impl Identifier {
+    pub fn new(s: impl Into<Box<str>>) -> Result<Self> {
+        let s = s.into();
+        if !is_valid(&s) {
+            bail!("Invalid identifier '{}'", s);
+        }
+        Ok(Self(s))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_string(self) -> String {
+        self.0.into()
+    }
+}
+
+impl fmt::Display for Identifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+/// Returns `true` if `s` is a valid identifier: starts with a letter or
+/// underscore, followed by any number of [`is_valid_identifier_char`]s.
+pub fn is_valid(s: &str) -> bool {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {}
+        _ => return false,
+    }
+    chars.all(is_valid_identifier_char)
+}
+
+pub fn is_valid_identifier_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
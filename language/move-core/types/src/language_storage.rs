@@ -0,0 +1,86 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{account_address::AccountAddress, identifier::Identifier};
+use std::{fmt, str::FromStr};
+
+/// A fully-qualified Move type, as it appears in on-chain storage and in
+/// transaction payloads.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TypeTag {
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    U256,
+    Address,
+    Signer,
+    Vector(Box<TypeTag>),
+    Struct(Box<StructTag>),
+}
+
+/// A fully-qualified struct name: the module that declares it plus its own
+/// name and type arguments.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StructTag {
+    pub address: AccountAddress,
+    pub module: Identifier,
+    pub name: Identifier,
+    pub type_params: Vec<TypeTag>,
+}
+
+impl fmt::Display for TypeTag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Bool => write!(f, "bool"),
+            Self::U8 => write!(f, "u8"),
+            Self::U16 => write!(f, "u16"),
+            Self::U32 => write!(f, "u32"),
+            Self::U64 => write!(f, "u64"),
+            Self::U128 => write!(f, "u128"),
+            Self::U256 => write!(f, "u256"),
+            Self::Address => write!(f, "address"),
+            Self::Signer => write!(f, "signer"),
+            Self::Vector(ty) => write!(f, "vector<{}>", ty),
+            Self::Struct(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl fmt::Display for StructTag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "0x{}::{}::{}",
+            self.address.short_str_lossless(),
+            self.module,
+            self.name
+        )?;
+        if let Some(first_ty) = self.type_params.first() {
+            write!(f, "<{}", first_ty)?;
+            for ty in &self.type_params[1..] {
+                write!(f, ", {}", ty)?;
+            }
+            write!(f, ">")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for TypeTag {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        crate::parser::parse_type_tag(s)
+    }
+}
+
+impl FromStr for StructTag {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        crate::parser::parse_struct_tag(s)
+    }
+}
@@ -0,0 +1,166 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A small, reusable lexer/parser framework.
+//!
+//! `parser.rs` uses this to implement Move's type-tag/transaction-argument
+//! grammar, but nothing here is specific to that grammar: any crate that
+//! embeds Move and needs a tiny DSL of its own (a script builder, a CLI
+//! flag, a test harness) can implement [`Token`] for its own token enum and
+//! get span-tagged tokens from [`tokenize`], [`Parser::consume`], [`Parser::peek`]
+//! and [`Parser::parse_comma_list`] for free, along with caret-annotated
+//! [`ParseError`]s. Dropping whitespace tokens (see [`Token::is_whitespace`])
+//! and appending an end-of-input token before building a [`Parser`] are not
+//! done here; each grammar does that itself.
+
+use std::{fmt, iter::Peekable};
+
+/// A parse error with the byte span of the source text it applies to, so
+/// that callers can render a caret-annotated diagnostic instead of a bare
+/// message.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    pub start: usize,
+    pub len: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(start: usize, len: usize, message: impl Into<String>) -> Self {
+        Self {
+            start,
+            len: len.max(1),
+            message: message.into(),
+        }
+    }
+
+    /// Shift this error's span by `by` bytes, used when a sub-parser's
+    /// locally-relative span is spliced back into the outer source string.
+    pub fn shift(mut self, by: usize) -> Self {
+        self.start += by;
+        self
+    }
+
+    /// Render this error as a caret-annotated snippet against `source`, in
+    /// the style of a compiler diagnostic:
+    ///
+    /// ```text
+    /// 0x1::M::S<<
+    ///           ^
+    /// unexpected token Lt, expected type tag
+    /// ```
+    pub fn annotated(&self, source: &str) -> String {
+        format!(
+            "{}\n{}{}\n{}",
+            source,
+            " ".repeat(self.start),
+            "^".repeat(self.len),
+            self.message
+        )
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (at byte {})", self.message, self.start)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A token kind produced by a particular grammar's lexer.
+pub trait Token: Eq + Clone + Sized {
+    /// Whether this token represents insignificant whitespace. `tokenize`
+    /// still emits these (so spans line up with the source), but a
+    /// `Parser` is usually built over a stream with them filtered out.
+    fn is_whitespace(&self) -> bool;
+
+    /// Scans a single token from the front of `s`, returning the token and
+    /// how many bytes of `s` it consumed, or `None` at end of input.
+    fn next_token(s: &str) -> Result<Option<(Self, usize)>, ParseError>;
+}
+
+/// Tokenizes all of `s` by repeatedly calling `Tok::next_token`, tagging
+/// each produced token with its `(start, len)` byte span within `s`.
+pub fn tokenize<Tok: Token>(s: &str) -> Result<Vec<(Tok, usize, usize)>, ParseError> {
+    let mut v = vec![];
+    let mut rest = s;
+    let mut offset = 0usize;
+    while let Some((tok, len)) = Tok::next_token(rest).map_err(|e| e.shift(offset))? {
+        v.push((tok, offset, len));
+        rest = &rest[len..];
+        offset += len;
+    }
+    Ok(v)
+}
+
+/// A generic recursive-descent parser over a peekable, span-tagged token
+/// stream. Grammars built on a [`Token`] impl get `consume`, `peek`, and
+/// `parse_comma_list` for free instead of re-implementing them.
+pub struct Parser<Tok, I: Iterator<Item = (Tok, usize, usize)>> {
+    it: Peekable<I>,
+}
+
+impl<Tok: Token + fmt::Debug, I: Iterator<Item = (Tok, usize, usize)>> Parser<Tok, I> {
+    pub fn new<T: IntoIterator<Item = (Tok, usize, usize), IntoIter = I>>(v: T) -> Self {
+        Self {
+            it: v.into_iter().peekable(),
+        }
+    }
+
+    pub fn advance(&mut self) -> Result<(Tok, usize, usize), ParseError> {
+        match self.it.next() {
+            Some(spanned) => Ok(spanned),
+            None => Err(ParseError::new(0, 1, "out of tokens, this should not happen")),
+        }
+    }
+
+    pub fn peek(&mut self) -> Option<&Tok> {
+        self.it.peek().map(|(tok, _, _)| tok)
+    }
+
+    pub fn peek_span(&mut self) -> Option<(usize, usize)> {
+        self.it.peek().map(|(_, start, len)| (*start, *len))
+    }
+
+    pub fn consume(&mut self, tok: Tok) -> Result<(), ParseError> {
+        let (t, start, len) = self.advance()?;
+        if t != tok {
+            return Err(ParseError::new(
+                start,
+                len,
+                format!("expected token {:?}, got {:?}", tok, t),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Parses a `comma`-separated list of items up to (but not including)
+    /// `end_token`, optionally allowing a trailing separator before it.
+    pub fn parse_comma_list<F, R>(
+        &mut self,
+        parse_list_item: F,
+        comma: Tok,
+        end_token: Tok,
+        allow_trailing_comma: bool,
+    ) -> Result<Vec<R>, ParseError>
+    where
+        F: Fn(&mut Self) -> Result<R, ParseError>,
+        R: fmt::Debug,
+    {
+        let mut v = vec![];
+        if self.peek() != Some(&end_token) {
+            loop {
+                v.push(parse_list_item(self)?);
+                if self.peek() == Some(&end_token) {
+                    break;
+                }
+                self.consume(comma.clone())?;
+                if self.peek() == Some(&end_token) && allow_trailing_comma {
+                    break;
+                }
+            }
+        }
+        Ok(v)
+    }
+}
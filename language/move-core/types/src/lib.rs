@@ -0,0 +1,11 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+pub mod account_address;
+pub mod identifier;
+pub mod language_storage;
+pub mod lexer;
+pub mod parser;
+pub mod safe_serialize;
+pub mod transaction_argument;
+pub mod u256;
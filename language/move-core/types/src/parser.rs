@@ -5,16 +5,24 @@ use crate::{
     account_address::AccountAddress,
     identifier::{self, Identifier},
     language_storage::{StructTag, TypeTag},
+    lexer::{self, ParseError},
     transaction_argument::TransactionArgument,
+    u256::U256,
 };
-use anyhow::{bail, format_err, Result};
-use std::iter::Peekable;
+use anyhow::{format_err, Result};
+use std::collections::BTreeMap;
 
-#[derive(Eq, PartialEq, Debug)]
-enum Token {
+pub use crate::lexer::ParseError as TypeTagParseError;
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Eq, PartialEq, Debug)]
+enum MoveToken {
     U8Type,
+    U16Type,
+    U32Type,
     U64Type,
     U128Type,
+    U256Type,
     BoolType,
     AddressType,
     VectorType,
@@ -23,8 +31,11 @@ enum Token {
     Name(String),
     Address(String),
     U8(String),
+    U16(String),
+    U32(String),
     U64(String),
     U128(String),
+    U256(String),
     Bytes(String),
     True,
     False,
@@ -32,31 +43,58 @@ enum Token {
     Lt,
     Gt,
     Comma,
+    LBracket,
+    RBracket,
     EOF,
 }
 
-impl Token {
-    fn is_whitespace(&self) -> bool {
-        matches!(self, Self::Whitespace(_))
+fn name_token(s: String) -> MoveToken {
+    match s.as_str() {
+        "u8" => MoveToken::U8Type,
+        "u16" => MoveToken::U16Type,
+        "u32" => MoveToken::U32Type,
+        "u64" => MoveToken::U64Type,
+        "u128" => MoveToken::U128Type,
+        "u256" => MoveToken::U256Type,
+        "bool" => MoveToken::BoolType,
+        "address" => MoveToken::AddressType,
+        "vector" => MoveToken::VectorType,
+        "true" => MoveToken::True,
+        "false" => MoveToken::False,
+        "signer" => MoveToken::SignerType,
+        _ => MoveToken::Name(s),
     }
 }
 
-fn name_token(s: String) -> Token {
-    match s.as_str() {
-        "u8" => Token::U8Type,
-        "u64" => Token::U64Type,
-        "u128" => Token::U128Type,
-        "bool" => Token::BoolType,
-        "address" => Token::AddressType,
-        "vector" => Token::VectorType,
-        "true" => Token::True,
-        "false" => Token::False,
-        "signer" => Token::SignerType,
-        _ => Token::Name(s),
+/// Module and member names are themselves plain identifiers (see
+/// `identifier::is_valid`), but `name_token` lexes a handful of them as type
+/// keywords instead of `Name` so that `vector<u8>` &c. parse as expected.
+/// A module or member position still needs to accept a name like `vector`
+/// or `bool`, so this maps a keyword token back to the source string it
+/// came from.
+fn token_as_name(tok: MoveToken) -> Option<String> {
+    match tok {
+        MoveToken::Name(s) => Some(s),
+        MoveToken::U8Type => Some("u8".to_string()),
+        MoveToken::U16Type => Some("u16".to_string()),
+        MoveToken::U32Type => Some("u32".to_string()),
+        MoveToken::U64Type => Some("u64".to_string()),
+        MoveToken::U128Type => Some("u128".to_string()),
+        MoveToken::U256Type => Some("u256".to_string()),
+        MoveToken::BoolType => Some("bool".to_string()),
+        MoveToken::AddressType => Some("address".to_string()),
+        MoveToken::VectorType => Some("vector".to_string()),
+        MoveToken::SignerType => Some("signer".to_string()),
+        MoveToken::True => Some("true".to_string()),
+        MoveToken::False => Some("false".to_string()),
+        _ => None,
     }
 }
 
-fn next_number(initial: char, mut it: impl Iterator<Item = char>) -> Result<(Token, usize)> {
+fn next_number(
+    initial: char,
+    mut it: impl Iterator<Item = char>,
+) -> Result<(MoveToken, usize), ParseError> {
     let mut num = String::new();
     num.push(initial);
     loop {
@@ -71,10 +109,13 @@ fn next_number(initial: char, mut it: impl Iterator<Item = char>) -> Result<(Tok
                         _ => {
                             let len = num.len() + suffix.len();
                             let tok = match suffix.as_str() {
-                                "u8" => Token::U8(num),
-                                "u64" => Token::U64(num),
-                                "u128" => Token::U128(num),
-                                _ => bail!("invalid suffix"),
+                                "u8" => MoveToken::U8(num),
+                                "u16" => MoveToken::U16(num),
+                                "u32" => MoveToken::U32(num),
+                                "u64" => MoveToken::U64(num),
+                                "u128" => MoveToken::U128(num),
+                                "u256" => MoveToken::U256(num),
+                                _ => return Err(ParseError::new(0, len, "invalid suffix")),
                             };
                             return Ok((tok, len));
                         }
@@ -83,24 +124,26 @@ fn next_number(initial: char, mut it: impl Iterator<Item = char>) -> Result<(Tok
             }
             _ => {
                 let len = num.len();
-                return Ok((Token::U64(num), len));
+                return Ok((MoveToken::U64(num), len));
             }
         }
     }
 }
 
 #[allow(clippy::many_single_char_names)]
-fn next_token(s: &str) -> Result<Option<(Token, usize)>> {
+fn next_move_token(s: &str) -> Result<Option<(MoveToken, usize)>, ParseError> {
     let mut it = s.chars().peekable();
     match it.next() {
         None => Ok(None),
         Some(c) => Ok(Some(match c {
-            '<' => (Token::Lt, 1),
-            '>' => (Token::Gt, 1),
-            ',' => (Token::Comma, 1),
+            '<' => (MoveToken::Lt, 1),
+            '>' => (MoveToken::Gt, 1),
+            ',' => (MoveToken::Comma, 1),
+            '[' => (MoveToken::LBracket, 1),
+            ']' => (MoveToken::RBracket, 1),
             ':' => match it.next() {
-                Some(':') => (Token::ColonColon, 2),
-                _ => bail!("unrecognized token"),
+                Some(':') => (MoveToken::ColonColon, 2),
+                _ => return Err(ParseError::new(0, 1, "unrecognized token")),
             },
             '0' if it.peek() == Some(&'x') || it.peek() == Some(&'X') => {
                 it.next().unwrap();
@@ -118,9 +161,9 @@ fn next_token(s: &str) -> Result<Option<(Token, usize)>> {
                             }
                         }
                         let len = r.len();
-                        (Token::Address(r), len)
+                        (MoveToken::Address(r), len)
                     }
-                    _ => bail!("unrecognized token"),
+                    _ => return Err(ParseError::new(0, 2, "unrecognized token")),
                 }
             }
             c if c.is_ascii_digit() => next_number(c, it)?,
@@ -131,11 +174,11 @@ fn next_token(s: &str) -> Result<Option<(Token, usize)>> {
                     match it.next() {
                         Some('"') => break,
                         Some(c) if c.is_ascii() => r.push(c),
-                        _ => bail!("unrecognized token"),
+                        _ => return Err(ParseError::new(0, r.len() + 2, "unrecognized token")),
                     }
                 }
                 let len = r.len() + 3;
-                (Token::Bytes(hex::encode(r)), len)
+                (MoveToken::Bytes(hex::encode(r)), len)
             }
             'x' if it.peek() == Some(&'"') => {
                 it.next().unwrap();
@@ -144,11 +187,11 @@ fn next_token(s: &str) -> Result<Option<(Token, usize)>> {
                     match it.next() {
                         Some('"') => break,
                         Some(c) if c.is_ascii_hexdigit() => r.push(c),
-                        _ => bail!("unrecognized token"),
+                        _ => return Err(ParseError::new(0, r.len() + 2, "unrecognized token")),
                     }
                 }
                 let len = r.len() + 3;
-                (Token::Bytes(r), len)
+                (MoveToken::Bytes(r), len)
             }
             c if c.is_ascii_whitespace() => {
                 let mut r = String::new();
@@ -161,7 +204,7 @@ fn next_token(s: &str) -> Result<Option<(Token, usize)>> {
                     }
                 }
                 let len = r.len();
-                (Token::Whitespace(r), len)
+                (MoveToken::Whitespace(r), len)
             }
             c if c.is_ascii_alphabetic() => {
                 let mut r = String::new();
@@ -176,204 +219,335 @@ fn next_token(s: &str) -> Result<Option<(Token, usize)>> {
                 let len = r.len();
                 (name_token(r), len)
             }
-            _ => bail!("unrecognized token"),
+            _ => return Err(ParseError::new(0, 1, "unrecognized token")),
         })),
     }
 }
 
-fn tokenize(mut s: &str) -> Result<Vec<Token>> {
-    let mut v = vec![];
-    while let Some((tok, n)) = next_token(s)? {
-        v.push(tok);
-        s = &s[n..];
+impl lexer::Token for MoveToken {
+    fn is_whitespace(&self) -> bool {
+        matches!(self, Self::Whitespace(_))
     }
-    Ok(v)
-}
 
-struct Parser<I: Iterator<Item = Token>> {
-    it: Peekable<I>,
+    fn next_token(s: &str) -> Result<Option<(Self, usize)>, ParseError> {
+        next_move_token(s)
+    }
 }
 
-impl<I: Iterator<Item = Token>> Parser<I> {
-    fn new<T: IntoIterator<Item = Token, IntoIter = I>>(v: T) -> Self {
-        Self {
-            it: v.into_iter().peekable(),
-        }
-    }
+type MoveParser = lexer::Parser<MoveToken, std::vec::IntoIter<(MoveToken, usize, usize)>>;
 
-    fn next(&mut self) -> Result<Token> {
-        match self.it.next() {
-            Some(tok) => Ok(tok),
-            None => bail!("out of tokens, this should not happen"),
+impl MoveParser {
+    fn parse_string(&mut self) -> Result<String, ParseError> {
+        let (t, start, len) = self.advance()?;
+        match t {
+            MoveToken::Name(s) => Ok(s),
+            tok => Err(ParseError::new(
+                start,
+                len,
+                format!("unexpected token {:?}, expected string", tok),
+            )),
         }
     }
 
-    fn peek(&mut self) -> Option<&Token> {
-        self.it.peek()
-    }
-
-    fn consume(&mut self, tok: Token) -> Result<()> {
-        let t = self.next()?;
-        if t != tok {
-            bail!("expected token {:?}, got {:?}", tok, t)
+    fn parse_type_tag(
+        &mut self,
+        depth: u8,
+        address_mapping: Option<&BTreeMap<String, AccountAddress>>,
+    ) -> Result<TypeTag, ParseError> {
+        if depth >= crate::safe_serialize::MAX_TYPE_TAG_NESTING {
+            let (start, len) = self.peek_span().unwrap_or((0, 1));
+            return Err(ParseError::new(
+                start,
+                len,
+                format!("exceeded TypeTag nesting limit during parsing: {}", depth),
+            ));
         }
-        Ok(())
+        let (tok, start, len) = self.advance()?;
+        Ok(match tok {
+            MoveToken::U8Type => TypeTag::U8,
+            MoveToken::U16Type => TypeTag::U16,
+            MoveToken::U32Type => TypeTag::U32,
+            MoveToken::U64Type => TypeTag::U64,
+            MoveToken::U128Type => TypeTag::U128,
+            MoveToken::U256Type => TypeTag::U256,
+            MoveToken::BoolType => TypeTag::Bool,
+            MoveToken::AddressType => TypeTag::Address,
+            MoveToken::SignerType => TypeTag::Signer,
+            MoveToken::VectorType => {
+                self.consume(MoveToken::Lt)?;
+                let ty = self.parse_type_tag(depth + 1, address_mapping)?;
+                self.consume(MoveToken::Gt)?;
+                TypeTag::Vector(Box::new(ty))
+            }
+            MoveToken::Address(addr) => {
+                let address = AccountAddress::from_hex_literal(&addr)
+                    .map_err(|e| ParseError::new(start, len, e.to_string()))?;
+                self.parse_struct_tag_tail(address, depth, address_mapping)?
+            }
+            MoveToken::Name(name) => {
+                let address = match address_mapping.and_then(|mapping| mapping.get(&name)) {
+                    Some(address) => *address,
+                    None => {
+                        return Err(ParseError::new(
+                            start,
+                            len,
+                            format!("address `{}` not in address mapping", name),
+                        ))
+                    }
+                };
+                self.parse_struct_tag_tail(address, depth, address_mapping)?
+            }
+            tok => {
+                return Err(ParseError::new(
+                    start,
+                    len,
+                    format!("unexpected token {:?}, expected type tag", tok),
+                ))
+            }
+        })
     }
 
-    fn parse_comma_list<F, R>(
+    /// Parses the `::module::Name<type_args>` tail of a struct tag once the
+    /// leading address has been resolved, whether from a hex literal or a
+    /// named address lookup.
+    fn parse_struct_tag_tail(
         &mut self,
-        parse_list_item: F,
-        end_token: Token,
-        allow_trailing_comma: bool,
-    ) -> Result<Vec<R>>
-    where
-        F: Fn(&mut Self) -> Result<R>,
-        R: std::fmt::Debug,
-    {
-        let mut v = vec![];
-        if !(self.peek() == Some(&end_token)) {
-            loop {
-                v.push(parse_list_item(self)?);
-                if self.peek() == Some(&end_token) {
-                    break;
-                }
-                self.consume(Token::Comma)?;
-                if self.peek() == Some(&end_token) && allow_trailing_comma {
-                    break;
+        address: AccountAddress,
+        depth: u8,
+        address_mapping: Option<&BTreeMap<String, AccountAddress>>,
+    ) -> Result<TypeTag, ParseError> {
+        self.consume(MoveToken::ColonColon)?;
+        let (module_tok, module_start, module_len) = self.advance()?;
+        let module_tok_debug = format!("{:?}", module_tok);
+        match token_as_name(module_tok) {
+            Some(module) => {
+                self.consume(MoveToken::ColonColon)?;
+                let (name_tok, name_start, name_len) = self.advance()?;
+                let name_tok_debug = format!("{:?}", name_tok);
+                match token_as_name(name_tok) {
+                    Some(name) => {
+                        let ty_args = if self.peek() == Some(&MoveToken::Lt) {
+                            self.advance()?;
+                            let ty_args = self.parse_comma_list(
+                                |parser| parser.parse_type_tag(depth + 1, address_mapping),
+                                MoveToken::Comma,
+                                MoveToken::Gt,
+                                true,
+                            )?;
+                            self.consume(MoveToken::Gt)?;
+                            ty_args
+                        } else {
+                            vec![]
+                        };
+                        Ok(TypeTag::Struct(Box::new(StructTag {
+                            address,
+                            module: Identifier::new(module).map_err(|e| {
+                                ParseError::new(module_start, module_len, e.to_string())
+                            })?,
+                            name: Identifier::new(name).map_err(|e| {
+                                ParseError::new(name_start, name_len, e.to_string())
+                            })?,
+                            type_params: ty_args,
+                        })))
+                    }
+                    None => Err(ParseError::new(
+                        name_start,
+                        name_len,
+                        format!("expected name, got {}", name_tok_debug),
+                    )),
                 }
             }
+            None => Err(ParseError::new(
+                module_start,
+                module_len,
+                format!("expected name, got {}", module_tok_debug),
+            )),
         }
-        Ok(v)
     }
 
-    fn parse_string(&mut self) -> Result<String> {
-        Ok(match self.next()? {
-            Token::Name(s) => s,
-            tok => bail!("unexpected token {:?}, expected string", tok),
-        })
-    }
-
-    fn parse_type_tag(&mut self, depth: u8) -> Result<TypeTag> {
-        if depth >= crate::safe_serialize::MAX_TYPE_TAG_NESTING {
-            bail!("Exceeded TypeTag nesting limit during parsing: {}", depth);
+    fn parse_transaction_argument(&mut self, depth: u8) -> Result<TransactionArgument, ParseError> {
+        if depth >= crate::safe_serialize::MAX_TRANSACTION_ARGUMENT_NESTING {
+            let (start, len) = self.peek_span().unwrap_or((0, 1));
+            return Err(ParseError::new(
+                start,
+                len,
+                format!(
+                    "exceeded transaction argument nesting limit during parsing: {}",
+                    depth
+                ),
+            ));
         }
-        Ok(match self.next()? {
-            Token::U8Type => TypeTag::U8,
-            Token::U64Type => TypeTag::U64,
-            Token::U128Type => TypeTag::U128,
-            Token::BoolType => TypeTag::Bool,
-            Token::AddressType => TypeTag::Address,
-            Token::SignerType => TypeTag::Signer,
-            Token::VectorType => {
-                self.consume(Token::Lt)?;
-                let ty = self.parse_type_tag(depth + 1)?;
-                self.consume(Token::Gt)?;
-                TypeTag::Vector(Box::new(ty))
-            }
-            Token::Address(addr) => {
-                self.consume(Token::ColonColon)?;
-                match self.next()? {
-                    Token::Name(module) => {
-                        self.consume(Token::ColonColon)?;
-                        match self.next()? {
-                            Token::Name(name) => {
-                                let ty_args = if self.peek() == Some(&Token::Lt) {
-                                    self.next()?;
-                                    let ty_args = self.parse_comma_list(
-                                        |parser| parser.parse_type_tag(depth + 1),
-                                        Token::Gt,
-                                        true,
-                                    )?;
-                                    self.consume(Token::Gt)?;
-                                    ty_args
-                                } else {
-                                    vec![]
-                                };
-                                TypeTag::Struct(Box::new(StructTag {
-                                    address: AccountAddress::from_hex_literal(&addr)?,
-                                    module: Identifier::new(module)?,
-                                    name: Identifier::new(name)?,
-                                    type_params: ty_args,
-                                }))
-                            }
-                            t => bail!("expected name, got {:?}", t),
-                        }
+        let (tok, start, len) = self.advance()?;
+        Ok(match tok {
+            MoveToken::U8(s) => TransactionArgument::U8(
+                s.parse()
+                    .map_err(|e: std::num::ParseIntError| ParseError::new(start, len, e.to_string()))?,
+            ),
+            MoveToken::U16(s) => TransactionArgument::U16(
+                s.parse()
+                    .map_err(|e: std::num::ParseIntError| ParseError::new(start, len, e.to_string()))?,
+            ),
+            MoveToken::U32(s) => TransactionArgument::U32(
+                s.parse()
+                    .map_err(|e: std::num::ParseIntError| ParseError::new(start, len, e.to_string()))?,
+            ),
+            MoveToken::U64(s) => TransactionArgument::U64(
+                s.parse()
+                    .map_err(|e: std::num::ParseIntError| ParseError::new(start, len, e.to_string()))?,
+            ),
+            MoveToken::U128(s) => TransactionArgument::U128(
+                s.parse()
+                    .map_err(|e: std::num::ParseIntError| ParseError::new(start, len, e.to_string()))?,
+            ),
+            MoveToken::U256(s) => TransactionArgument::U256(
+                U256::from_decimal_str(&s)
+                    .ok_or_else(|| ParseError::new(start, len, "invalid u256 literal"))?,
+            ),
+            MoveToken::True => TransactionArgument::Bool(true),
+            MoveToken::False => TransactionArgument::Bool(false),
+            MoveToken::Address(addr) => TransactionArgument::Address(
+                AccountAddress::from_hex_literal(&addr)
+                    .map_err(|e| ParseError::new(start, len, e.to_string()))?,
+            ),
+            MoveToken::Bytes(s) => TransactionArgument::U8Vector(
+                hex::decode(s).map_err(|e| ParseError::new(start, len, e.to_string()))?,
+            ),
+            MoveToken::VectorType => {
+                self.consume(MoveToken::LBracket)?;
+                let (elems_start, _) = self.peek_span().unwrap_or((start, len));
+                let elems = self.parse_comma_list(
+                    |parser| parser.parse_transaction_argument(depth + 1),
+                    MoveToken::Comma,
+                    MoveToken::RBracket,
+                    true,
+                )?;
+                let (end_start, end_len) = self.peek_span().unwrap_or((elems_start, 1));
+                self.consume(MoveToken::RBracket)?;
+                if let Some(first) = elems.first() {
+                    let discriminant = std::mem::discriminant(first);
+                    if elems.iter().any(|e| std::mem::discriminant(e) != discriminant) {
+                        return Err(ParseError::new(
+                            elems_start,
+                            end_start + end_len - elems_start,
+                            "vector literal elements must all have the same type",
+                        ));
                     }
-                    t => bail!("expected name, got {:?}", t),
+                }
+                // An empty `vector[]` has no elements to infer a `u8` type
+                // from, so it parses as a general `Vector` (matching the
+                // empty case of `Vector`'s `Display` impl); write `x""` for
+                // an empty byte vector instead.
+                let all_u8 = elems.iter().all(|e| matches!(e, TransactionArgument::U8(_)));
+                if !elems.is_empty() && all_u8 {
+                    TransactionArgument::U8Vector(
+                        elems
+                            .into_iter()
+                            .map(|e| match e {
+                                TransactionArgument::U8(b) => b,
+                                _ => unreachable!(),
+                            })
+                            .collect(),
+                    )
+                } else {
+                    TransactionArgument::Vector(elems)
                 }
             }
-            tok => bail!("unexpected token {:?}, expected type tag", tok),
-        })
-    }
-
-    fn parse_transaction_argument(&mut self) -> Result<TransactionArgument> {
-        Ok(match self.next()? {
-            Token::U8(s) => TransactionArgument::U8(s.parse()?),
-            Token::U64(s) => TransactionArgument::U64(s.parse()?),
-            Token::U128(s) => TransactionArgument::U128(s.parse()?),
-            Token::True => TransactionArgument::Bool(true),
-            Token::False => TransactionArgument::Bool(false),
-            Token::Address(addr) => {
-                TransactionArgument::Address(AccountAddress::from_hex_literal(&addr)?)
+            tok => {
+                return Err(ParseError::new(
+                    start,
+                    len,
+                    format!("unexpected token {:?}, expected transaction argument", tok),
+                ))
             }
-            Token::Bytes(s) => TransactionArgument::U8Vector(hex::decode(s)?),
-            tok => bail!("unexpected token {:?}, expected transaction argument", tok),
         })
     }
 }
 
 fn parse<F, T>(s: &str, f: F) -> Result<T>
 where
-    F: Fn(&mut Parser<std::vec::IntoIter<Token>>) -> Result<T>,
+    F: Fn(&mut MoveParser) -> Result<T, ParseError>,
 {
-    let mut tokens: Vec<_> = tokenize(s)?
+    use lexer::Token as _;
+
+    let mut tokens: Vec<_> = lexer::tokenize(s)?
         .into_iter()
-        .filter(|tok| !tok.is_whitespace())
+        .filter(|(tok, _, _): &(MoveToken, usize, usize)| !tok.is_whitespace())
         .collect();
-    tokens.push(Token::EOF);
-    let mut parser = Parser::new(tokens);
+    tokens.push((MoveToken::EOF, s.len(), 0));
+    let mut parser = MoveParser::new(tokens);
     let res = f(&mut parser)?;
-    parser.consume(Token::EOF)?;
+    parser.consume(MoveToken::EOF)?;
     Ok(res)
 }
 
 pub fn parse_string_list(s: &str) -> Result<Vec<String>> {
     parse(s, |parser| {
-        parser.parse_comma_list(|parser| parser.parse_string(), Token::EOF, true)
+        parser.parse_comma_list(|parser| parser.parse_string(), MoveToken::Comma, MoveToken::EOF, true)
     })
 }
 
 pub fn parse_type_tags(s: &str) -> Result<Vec<TypeTag>> {
     parse(s, |parser| {
-        parser.parse_comma_list(|parser| parser.parse_type_tag(0), Token::EOF, true)
+        parser.parse_comma_list(
+            |parser| parser.parse_type_tag(0, None),
+            MoveToken::Comma,
+            MoveToken::EOF,
+            true,
+        )
     })
 }
 
 pub fn parse_type_tag(s: &str) -> Result<TypeTag> {
-    parse(s, |parser| parser.parse_type_tag(0))
+    parse(s, |parser| parser.parse_type_tag(0, None))
+}
+
+/// Like [`parse_type_tag`], but additionally accepts named addresses (e.g.
+/// `Std::vector::T`), resolving each through `addresses` and erroring on any
+/// name not found there.
+pub fn parse_type_tag_with_addresses(
+    s: &str,
+    addresses: &BTreeMap<String, AccountAddress>,
+) -> Result<TypeTag> {
+    parse(s, |parser| parser.parse_type_tag(0, Some(addresses)))
 }
 
 pub fn parse_transaction_arguments(s: &str) -> Result<Vec<TransactionArgument>> {
     parse(s, |parser| {
         parser.parse_comma_list(
-            |parser| parser.parse_transaction_argument(),
-            Token::EOF,
+            |parser| parser.parse_transaction_argument(0),
+            MoveToken::Comma,
+            MoveToken::EOF,
             true,
         )
     })
 }
 
 pub fn parse_transaction_argument(s: &str) -> Result<TransactionArgument> {
-    parse(s, |parser| parser.parse_transaction_argument())
+    parse(s, |parser| parser.parse_transaction_argument(0))
 }
 
 pub fn parse_struct_tag(s: &str) -> Result<StructTag> {
-    let type_tag = parse(s, |parser| parser.parse_type_tag(0))
+    let type_tag = parse(s, |parser| parser.parse_type_tag(0, None))
+        .map_err(|e| format_err!("invalid struct tag: {}, {}", s, e))?;
+    if let TypeTag::Struct(struct_tag) = type_tag {
+        Ok(*struct_tag)
+    } else {
+        Err(format_err!("invalid struct tag: {}", s))
+    }
+}
+
+/// Like [`parse_struct_tag`], but additionally accepts named addresses,
+/// resolving each through `addresses`. See [`parse_type_tag_with_addresses`].
+pub fn parse_struct_tag_with_addresses(
+    s: &str,
+    addresses: &BTreeMap<String, AccountAddress>,
+) -> Result<StructTag> {
+    let type_tag = parse(s, |parser| parser.parse_type_tag(0, Some(addresses)))
         .map_err(|e| format_err!("invalid struct tag: {}, {}", s, e))?;
     if let TypeTag::Struct(struct_tag) = type_tag {
         Ok(*struct_tag)
     } else {
-        bail!("invalid struct tag: {}", s)
+        Err(format_err!("invalid struct tag: {}", s))
     }
 }
 
@@ -381,8 +555,9 @@ pub fn parse_struct_tag(s: &str) -> Result<StructTag> {
 mod tests {
     use crate::{
         account_address::AccountAddress,
-        parser::{parse_struct_tag, parse_transaction_argument, parse_type_tag},
+        parser::{parse_struct_tag, parse_transaction_argument, parse_type_tag, TypeTagParseError},
         transaction_argument::TransactionArgument,
+        u256::U256,
     };
 
     #[allow(clippy::unreadable_literal)]
@@ -399,11 +574,25 @@ mod tests {
             ("0u64", T::U64(0)),
             ("18446744073709551615", T::U64(18446744073709551615)),
             ("18446744073709551615u64", T::U64(18446744073709551615)),
+            ("0u16", T::U16(0)),
+            ("65535u16", T::U16(65535)),
+            ("0u32", T::U32(0)),
+            ("4294967295u32", T::U32(4294967295)),
             ("0u128", T::U128(0)),
             (
                 "340282366920938463463374607431768211455u128",
                 T::U128(340282366920938463463374607431768211455),
             ),
+            ("0u256", T::U256(U256::from_decimal_str("0").unwrap())),
+            (
+                "115792089237316195423570985008687907853269984665640564039457584007913129639935u256",
+                T::U256(
+                    U256::from_decimal_str(
+                        "115792089237316195423570985008687907853269984665640564039457584007913129639935",
+                    )
+                    .unwrap(),
+                ),
+            ),
             ("true", T::Bool(true)),
             ("false", T::Bool(false)),
             (
@@ -422,6 +611,23 @@ mod tests {
             ("x\"\"", T::U8Vector(vec![])),
             ("x\"00\"", T::U8Vector(vec![0x00])),
             ("x\"deadbeef\"", T::U8Vector(vec![0xde, 0xad, 0xbe, 0xef])),
+            ("vector[]", T::Vector(vec![])),
+            ("vector[1u8, 2u8, 3u8]", T::U8Vector(vec![1, 2, 3])),
+            (
+                "vector[1, 2, 3]",
+                T::Vector(vec![T::U64(1), T::U64(2), T::U64(3)]),
+            ),
+            (
+                "vector[0x1, 0x2]",
+                T::Vector(vec![
+                    T::Address(AccountAddress::from_hex_literal("0x1").unwrap()),
+                    T::Address(AccountAddress::from_hex_literal("0x2").unwrap()),
+                ]),
+            ),
+            (
+                "vector[vector[1u8], vector[2u8]]",
+                T::Vector(vec![T::U8Vector(vec![1]), T::U8Vector(vec![2])]),
+            ),
         ] {
             assert_eq!(&parse_transaction_argument(s).unwrap(), expected)
         }
@@ -437,9 +643,12 @@ mod tests {
             "0u6 4",
             "0u",
             "256u8",
+            "65536u16",
+            "4294967296u32",
             "18446744073709551616",
             "18446744073709551616u64",
             "340282366920938463463374607431768211456u128",
+            "115792089237316195423570985008687907853269984665640564039457584007913129639936u256",
             "0xg",
             "0x00g0",
             "0x",
@@ -455,15 +664,62 @@ mod tests {
             "3false",
             "3 false",
             "",
+            "vector[1u8, true]",
+            "vector[1, 2,",
+            "vector(1, 2)",
         ] {
             assert!(parse_transaction_argument(s).is_err())
         }
     }
 
+    #[test]
+    fn test_transaction_argument_vector_round_trips_through_display() {
+        use TransactionArgument as T;
+
+        for arg in &[
+            T::U8Vector(vec![]),
+            T::U8Vector(vec![1, 2, 3]),
+            T::Vector(vec![]),
+            T::Vector(vec![T::U64(1), T::U64(2), T::U64(3)]),
+            T::Vector(vec![T::U8Vector(vec![1]), T::U8Vector(vec![2])]),
+        ] {
+            let displayed = arg.to_string();
+            assert_eq!(
+                &parse_transaction_argument(&displayed).unwrap(),
+                arg,
+                "{} did not round-trip",
+                displayed
+            );
+        }
+    }
+
+    #[test]
+    fn test_transaction_argument_empty_vector_literal_is_general_vector() {
+        use TransactionArgument as T;
+
+        // An empty bracketed literal has no elements to infer `u8` from, so
+        // it parses as an empty general `Vector`, not an empty `U8Vector`;
+        // `x""` is the unambiguous way to write an empty byte vector.
+        assert_eq!(parse_transaction_argument("vector[]").unwrap(), T::Vector(vec![]));
+        assert_eq!(parse_transaction_argument("x\"\"").unwrap(), T::U8Vector(vec![]));
+    }
+
+    #[test]
+    fn test_transaction_argument_vector_nesting_limit() {
+        let nested = "vector[".repeat(20) + "1" + &"]".repeat(20);
+        assert!(
+            parse_transaction_argument(&nested).is_err(),
+            "should have exceeded the vector nesting limit"
+        );
+    }
+
     #[test]
     fn test_type_tag() {
         for s in &[
+            "u16",
+            "u32",
             "u64",
+            "u256",
             "bool",
             "vector<u8>",
             "vector<vector<u64>>",
@@ -534,6 +790,49 @@ mod tests {
             parse_struct_tag(s).is_err(),
             "Should have failed to parse type tag {}",
             s
-        );        
+        );
+    }
+
+    #[test]
+    fn test_parse_type_tag_with_named_addresses() {
+        use crate::parser::{parse_struct_tag_with_addresses, parse_type_tag_with_addresses};
+        use std::collections::BTreeMap;
+
+        let mut addresses = BTreeMap::new();
+        addresses.insert("Std".to_string(), AccountAddress::from_hex_literal("0x1").unwrap());
+        addresses.insert("DiemFramework".to_string(), AccountAddress::from_hex_literal("0x2").unwrap());
+
+        assert_eq!(
+            parse_type_tag_with_addresses("Std::vector::T", &addresses)
+                .unwrap()
+                .to_string(),
+            "0x1::vector::T",
+        );
+        assert_eq!(
+            parse_struct_tag_with_addresses("DiemFramework::M::S<Std::vector::T>", &addresses)
+                .unwrap()
+                .to_string(),
+            "0x2::M::S<0x1::vector::T>",
+        );
+
+        // An unknown name is a hard error, not a fallback to a literal address.
+        assert!(parse_type_tag_with_addresses("Unbound::M::S", &addresses).is_err());
+
+        // The no-map entry points keep rejecting named addresses entirely.
+        assert!(parse_type_tag("Std::vector::T").is_err());
+        assert!(parse_struct_tag("Std::vector::T").is_err());
+    }
+
+    #[test]
+    fn test_parse_error_points_at_offending_span() {
+        // The second `<` at byte offset 10 is where parsing the type
+        // argument to `S<...>` fails.
+        let err = parse_type_tag("0x1::M::S<<").unwrap_err();
+        let parse_error = err
+            .downcast_ref::<TypeTagParseError>()
+            .expect("parse_type_tag should fail with a ParseError");
+        assert_eq!(parse_error.start, 10);
+        let annotated = parse_error.annotated("0x1::M::S<<");
+        assert!(annotated.contains('^'));
     }
 }
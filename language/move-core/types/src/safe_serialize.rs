@@ -0,0 +1,18 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Bounds shared by (de)serialization and parsing so that a maliciously
+//! crafted or merely accidental deeply-nested `TypeTag` can't blow the
+//! stack.
+
+/// The depth at which parsing a `TypeTag` fails: the outermost tag is at
+/// depth 0 and each `vector<...>` / generic type-parameter level adds one,
+/// so at most 12 such levels fit around the innermost tag. 13 is the
+/// shallowest bound that still accepts the deepest struct tag in
+/// `test_parse_valid_struct_tag`.
+pub const MAX_TYPE_TAG_NESTING: u8 = 13;
+
+/// The depth at which parsing a `TransactionArgument` value literal fails:
+/// the outermost value is at depth 0 and each `vector[...]` adds one, so at
+/// most 13 `vector[...]` levels fit around a scalar element.
+pub const MAX_TRANSACTION_ARGUMENT_NESTING: u8 = 14;
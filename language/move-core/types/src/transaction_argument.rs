@@ -0,0 +1,55 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{account_address::AccountAddress, u256::U256};
+use std::{fmt, str::FromStr};
+
+/// An argument to a Move script or transaction script function, as supplied
+/// on the command line or over an RPC boundary.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TransactionArgument {
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    U256(U256),
+    Address(AccountAddress),
+    U8Vector(Vec<u8>),
+    Bool(bool),
+    Vector(Vec<TransactionArgument>),
+}
+
+impl fmt::Display for TransactionArgument {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::U8(v) => write!(f, "{}u8", v),
+            Self::U16(v) => write!(f, "{}u16", v),
+            Self::U32(v) => write!(f, "{}u32", v),
+            Self::U64(v) => write!(f, "{}", v),
+            Self::U128(v) => write!(f, "{}u128", v),
+            Self::U256(v) => write!(f, "{}u256", v),
+            Self::Bool(v) => write!(f, "{}", v),
+            Self::Address(a) => write!(f, "0x{}", a.short_str_lossless()),
+            Self::U8Vector(v) => write!(f, "x\"{}\"", hex::encode(v)),
+            Self::Vector(v) => {
+                write!(f, "vector[")?;
+                for (i, arg) in v.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", arg)?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+impl FromStr for TransactionArgument {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        crate::parser::parse_transaction_argument(s)
+    }
+}
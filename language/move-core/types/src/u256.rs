@@ -0,0 +1,119 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! A minimal unsigned 256-bit integer, used to represent Move's `u256`
+//! values. This only implements the handful of operations the rest of the
+//! crate needs (parsing a decimal literal, range-checking it, and printing
+//! it back out) rather than pulling in a full bignum dependency.
+
+use std::fmt;
+
+/// An unsigned 256-bit integer, stored as four little-endian `u64` limbs.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct U256([u64; 4]);
+
+impl U256 {
+    pub const ZERO: Self = Self([0; 4]);
+    pub const MAX: Self = Self([u64::MAX; 4]);
+
+    /// Parses a decimal digit string into a `U256`, returning `None` if the
+    /// value doesn't fit in 256 bits (i.e. is `>= 2^256`).
+    pub fn from_decimal_str(s: &str) -> Option<Self> {
+        let mut acc = Self::ZERO;
+        for c in s.chars() {
+            let digit = u64::from(c.to_digit(10)?);
+            acc = acc.checked_mul10()?.checked_add_u64(digit)?;
+        }
+        Some(acc)
+    }
+
+    fn checked_add(self, rhs: Self) -> Option<Self> {
+        let mut out = [0u64; 4];
+        let mut carry = 0u128;
+        for ((out, lhs), rhs) in out.iter_mut().zip(self.0).zip(rhs.0) {
+            let sum = u128::from(lhs) + u128::from(rhs) + carry;
+            *out = sum as u64;
+            carry = sum >> 64;
+        }
+        if carry != 0 {
+            None
+        } else {
+            Some(Self(out))
+        }
+    }
+
+    fn checked_add_u64(self, rhs: u64) -> Option<Self> {
+        self.checked_add(Self([rhs, 0, 0, 0]))
+    }
+
+    fn checked_mul10(self) -> Option<Self> {
+        // x * 10 == x << 3 (x * 8) + x << 1 (x * 2)
+        self.checked_shl(3)?.checked_add(self.checked_shl(1)?)
+    }
+
+    fn checked_shl(self, bits: u32) -> Option<Self> {
+        if self == Self::ZERO || bits == 0 {
+            return Some(self);
+        }
+        let mut out = [0u64; 4];
+        let mut carry = 0u64;
+        for (out, limb) in out.iter_mut().zip(self.0) {
+            *out = (limb << bits) | carry;
+            carry = limb >> (64 - bits);
+        }
+        if carry != 0 {
+            None
+        } else {
+            Some(Self(out))
+        }
+    }
+
+    fn div_rem_u64(self, divisor: u64) -> (Self, u64) {
+        let mut rem: u128 = 0;
+        let mut out = [0u64; 4];
+        for i in (0..4).rev() {
+            let cur = (rem << 64) | u128::from(self.0[i]);
+            out[i] = (cur / u128::from(divisor)) as u64;
+            rem = cur % u128::from(divisor);
+        }
+        (Self(out), rem as u64)
+    }
+}
+
+impl fmt::Display for U256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if *self == Self::ZERO {
+            return f.write_str("0");
+        }
+        let mut digits = vec![];
+        let mut value = *self;
+        while value != Self::ZERO {
+            let (quotient, remainder) = value.div_rem_u64(10);
+            digits.push(std::char::from_digit(remainder as u32, 10).unwrap());
+            value = quotient;
+        }
+        digits.reverse();
+        f.write_str(&digits.into_iter().collect::<String>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::U256;
+
+    #[test]
+    fn round_trips_decimal_strings() {
+        for s in &["0", "1", "255", "65536", "340282366920938463463374607431768211456"] {
+            assert_eq!(&U256::from_decimal_str(s).unwrap().to_string(), s);
+        }
+    }
+
+    #[test]
+    fn rejects_values_that_overflow_256_bits() {
+        let max = "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+        let overflow =
+            "115792089237316195423570985008687907853269984665640564039457584007913129639936";
+        assert_eq!(U256::from_decimal_str(max).unwrap(), U256::MAX);
+        assert!(U256::from_decimal_str(overflow).is_none());
+    }
+}